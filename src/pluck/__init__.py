@@ -24,11 +24,18 @@ See the README.md on GitHub for more information.
 """
 
 from . import client
+from ._json import JsonPath, get_path
+from ._normalization import column_name, normalize, selection_from_example
 from ._pluck import create, execute, Response
 
 __all__ = (
     "create",
     "execute",
     "Response",
+    "JsonPath",
+    "normalize",
+    "column_name",
+    "selection_from_example",
+    "get_path",
     "client",
 )
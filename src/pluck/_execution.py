@@ -13,9 +13,10 @@ from ._json import (
     JsonScalar,
     JsonValue,
     JsonVisitor,
+    is_array,
     visit,
 )
-from ._normalization import normalize
+from ._normalization import column_name, normalize
 from ._parser import ParsedQuery, QueryParser
 from ._decorators import timeit
 from .client import GraphQLClient, GraphQLRequest, GraphQLResponse, UrllibGraphQLClient
@@ -77,27 +78,32 @@ class Executor:
         separator = self._options.separator
         frames = {}
         for name, data in frame_data.items():
-            selection_set = (
-                query.selection_set
-                if query.is_implicit_mode
-                else query.frame(name).selection_set
-            )
+            info = query if query.is_implicit_mode else query.frame(name)
             data = itertools.chain(
                 *[
                     normalize(
                         x,
                         separator,
                         fallback=name,
-                        selection_set=selection_set,
+                        selection_set=info.selection_set,
                     )
                     for x in data
+                    if not _is_empty(x)
                 ]
             )
-            frames[name] = self._create_data_frame(data)
+            data = list(data)
+            if query.is_implicit_mode and not any(data):
+                data = []
+            columns = [column_name(x, separator, fallback=name) for x in info.fields]
+            frames[name] = self._create_data_frame(data, columns)
         return frames
 
     @timeit
-    def _create_data_frame(self, data) -> DataFrame:
+    def _create_data_frame(self, data: List[Dict], columns: List[str]) -> DataFrame:
+        if not data:
+            return self._options.library.create_empty(columns)
+        if not any(data):
+            data = [dict.fromkeys(columns) for _ in data]
         return self._options.library.create(data)
 
     @timeit
@@ -151,6 +157,8 @@ class FrameExtractor(JsonVisitor):
             if not self._ctx.has_nested_frame(path):
                 return STOP
             self._captured[path] = value
+        elif not self._ctx.has_nested_frame(path):
+            return STOP
 
     def leave(self, path: JsonPath, value: JsonValue):
         captured = self._captured.get(path)
@@ -158,6 +166,10 @@ class FrameExtractor(JsonVisitor):
             del self._captured[path]
 
 
+def _is_empty(value: JsonValue) -> bool:
+    return value is None or (is_array(value) and all(x is None for x in value))
+
+
 def get_column_names(mode):
     match mode:
         case None | "full" | "FULL":
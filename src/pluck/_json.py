@@ -1,9 +1,13 @@
 from __future__ import annotations
 
+import dataclasses
 import enum
+import itertools
+import types
 from abc import ABC, abstractmethod
 from collections import deque
-from typing import Any, Dict, List, Optional, Union, TextIO
+from collections.abc import Iterable, Mapping
+from typing import Any, Dict, Iterator, List, Optional, Tuple, Union, TextIO
 
 JsonObject = Dict[str, Any]
 JsonArray = List[Any]
@@ -33,6 +37,15 @@ class JsonVisitorAction(enum.Enum):
 
 
 STOP = JsonVisitorAction.STOP
+MISSING = object()
+NOT_COERCIBLE = (
+    type,
+    enum.Enum,
+    types.FunctionType,
+    types.BuiltinFunctionType,
+    types.MethodType,
+    types.ModuleType,
+)
 
 
 class JsonVisitor:
@@ -60,13 +73,85 @@ class JsonVisitor:
         pass
 
 
-def visit(root, visitor: JsonVisitor, initial_path: JsonPath = None):
-    JsonWalker(visitor).walk(root, initial_path)
+def visit(
+    root,
+    visitor: JsonVisitor,
+    initial_path: JsonPath = None,
+    scalar_types: Tuple[type, ...] = (),
+    coerce_objects: bool = False,
+    max_items: Optional[int] = None,
+):
+    walker = JsonWalker(visitor, scalar_types, coerce_objects, max_items)
+    walker.walk(root, initial_path)
+
+
+def is_array(obj) -> bool:
+    return isinstance(obj, Iterable) and not isinstance(
+        obj, (str, bytes, bytearray, Mapping)
+    )
+
+
+def get_path(obj: JsonValue, path: Iterable[str]) -> JsonValue:
+    """
+    Get the value at `path` within `obj` (or `None` if any segment is missing).
+
+    Arrays along the path are fanned out, so a list of the values found within each
+    of their items is returned instead (e.g. `["a", "b"]` within `{"a": [{"b": 1},
+    {"b": 2}, {}]}` is `[1, 2]`).
+    """
+    values, is_fanned_out = [obj], False
+    for key in path:
+        found = []
+        for value in values:
+            if is_array(value):
+                is_fanned_out = True
+                items = _flatten(value)
+            else:
+                items = (value,)
+            for item in items:
+                if isinstance(item, Mapping):
+                    if (found_value := get_key(item, key, MISSING)) is not MISSING:
+                        found.append(found_value)
+        values = found
+    if is_fanned_out:
+        return values
+    return values[0] if values else None
+
+
+def get_key(obj: Mapping, key: str, default: Any = None) -> Any:
+    """
+    Get the value of `key` within `obj`, where a key that is not a string matches its
+    `str` (as it does when walked).
+    """
+    if key in obj:
+        return obj[key]
+    return next((v for k, v in obj.items() if to_segment(k) == key), default)
+
+
+def to_segment(key: Any) -> str:
+    return key if isinstance(key, str) else str(key)
+
+
+def _flatten(array) -> Iterator:
+    for item in array:
+        if is_array(item):
+            yield from _flatten(item)
+        else:
+            yield item
 
 
 class JsonWalker:
-    def __init__(self, visitor: JsonVisitor):
+    def __init__(
+        self,
+        visitor: JsonVisitor,
+        scalar_types: Tuple[type, ...] = (),
+        coerce_objects: bool = False,
+        max_items: Optional[int] = None,
+    ):
         self._visitor = visitor
+        self._scalar_types = (str, float, int, bool, *scalar_types)
+        self._coerce_objects = coerce_objects
+        self._max_items = max_items
 
     def walk(self, root: JsonValue, initial_path: JsonPath = None):
         stack = deque()
@@ -92,28 +177,80 @@ class JsonWalker:
                 visitor.on_null(path)
 
             elif self._is_object_value(current):
+                current = self._as_object(current)
                 if visitor.enter_object(path, current) != STOP:
                     put(path, current, leave=True)
-                    for key, value in reversed(current.items()):
+                    for key, value in reversed(self._items(path, current)):
                         put(path.add(key), value)
 
             elif self._is_array_value(current):
+                current = self._as_array(current)
                 if visitor.enter_array(path, current) != STOP:
                     put(path, current, leave=True)
                     for value in reversed(current):
                         put(path, value)
 
+    def _is_scalar_value(self, obj) -> bool:
+        return isinstance(obj, self._scalar_types)
+
+    def _is_object_value(self, obj) -> bool:
+        if isinstance(obj, dict):
+            return True
+        return self._coerce_objects and self._is_coercible(obj)
+
+    @staticmethod
+    def _is_coercible(obj) -> bool:
+        if isinstance(obj, NOT_COERCIBLE) or hasattr(type(obj), "__iter__"):
+            return False
+        return dataclasses.is_dataclass(obj) or hasattr(obj, "__dict__")
+
     @staticmethod
-    def _is_scalar_value(obj) -> bool:
-        return isinstance(obj, (str, float, int, bool))
+    def _as_object(obj) -> JsonObject:
+        if isinstance(obj, dict):
+            return obj
+        if dataclasses.is_dataclass(obj):
+            items = ((f.name, getattr(obj, f.name)) for f in dataclasses.fields(obj))
+        else:
+            items = vars(obj).items()
+        return {k: v for k, v in items if not k.startswith("_")}
 
     @staticmethod
-    def _is_object_value(obj) -> bool:
-        return isinstance(obj, dict)
+    def _items(path: JsonPath, obj: JsonObject) -> List[Tuple[str, Any]]:
+        if all(isinstance(key, str) for key in obj):
+            return list(obj.items())
+        items = {}
+        for key, value in obj.items():
+            segment = to_segment(key)
+            if segment in items:
+                other = next(k for k in obj if to_segment(k) == segment)
+                raise ValueError(
+                    f"The keys {other!r} and {key!r} at '{path}' are both "
+                    f"named '{segment}'."
+                )
+            items[segment] = value
+        return list(items.items())
 
     @staticmethod
     def _is_array_value(obj) -> bool:
-        return isinstance(obj, list)
+        return is_array(obj)
+
+    def _as_array(self, obj) -> JsonArray:
+        if isinstance(obj, (list, tuple)):
+            return obj
+        if isinstance(obj, (set, frozenset)):
+            try:
+                return sorted(obj)
+            except TypeError:
+                return list(obj)
+        return self._materialize(obj)
+
+    def _materialize(self, obj) -> JsonArray:
+        if (max_items := self._max_items) is None:
+            return list(obj)
+        items = list(itertools.islice(obj, max_items + 1))
+        if len(items) > max_items:
+            raise ValueError(f"An iterable has more than {max_items} items.")
+        return items
 
 
 class JsonSerializer(ABC):
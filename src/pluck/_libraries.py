@@ -1,5 +1,5 @@
 from abc import ABC, abstractmethod
-from typing import Dict, Iterable
+from typing import Dict, Iterable, List
 
 import pandas as pd
 
@@ -14,6 +14,10 @@ class DataFrameLibrary(ABC):
     def create(self, data: Records) -> DataFrame:
         raise NotImplementedError()
 
+    @abstractmethod
+    def create_empty(self, columns: List[str]) -> DataFrame:
+        raise NotImplementedError()
+
     @abstractmethod
     def rename(self, df: DataFrame, columns: dict[str, str]) -> DataFrame:
         raise NotImplementedError()
@@ -23,5 +27,8 @@ class PandasDataFrameLibrary(DataFrameLibrary):
     def create(self, data: Records) -> DataFrame:
         return pd.DataFrame(data)
 
+    def create_empty(self, columns: List[str]) -> DataFrame:
+        return pd.DataFrame(columns=columns)
+
     def rename(self, df: DataFrame, columns: dict[str, str]) -> DataFrame:
         return df.rename(columns=columns)
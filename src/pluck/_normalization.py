@@ -1,12 +1,38 @@
 from __future__ import annotations
 
 import itertools
+import re
+import unicodedata
 from dataclasses import dataclass, replace
-from typing import Dict, Generator, Iterable, List, Optional, Set, Tuple
+from typing import (
+    Callable,
+    Dict,
+    FrozenSet,
+    Generator,
+    Iterable,
+    List,
+    Optional,
+    Set,
+    Tuple,
+    Union,
+)
 
-from ._json import STOP, JsonArray, JsonPath, JsonScalar, JsonValue, JsonVisitor, visit
+from ._json import (
+    MISSING,
+    STOP,
+    JsonArray,
+    JsonObject,
+    JsonPath,
+    JsonScalar,
+    JsonValue,
+    JsonVisitor,
+    get_key,
+    is_array,
+    visit,
+)
 
 NormalizeResult = List[Dict[str, JsonValue]]
+NormalizePaths = Dict[str, List[str]]
 
 
 def normalize(
@@ -14,11 +40,184 @@ def normalize(
     separator: str = ".",
     fallback: Optional[str] = "?",
     selection_set: Optional[Set[JsonPath]] = None,
-) -> NormalizeResult:
+    *,
+    prefix: Optional[str] = None,
+    array_marker: Optional[str] = None,
+    empty_list_as_null: bool = False,
+    empty_object_as_null: bool = False,
+    sanitize: Optional[str] = None,
+    max_rows: Optional[int] = None,
+    sibling_mode: str = "product",
+    aliases: Optional[Dict[str, str]] = None,
+    max_depth: Optional[int] = None,
+    with_paths: bool = False,
+    null_value: JsonValue = None,
+    list_delimiter: Optional[str] = None,
+    drop_null_columns: bool = False,
+    escape_separator: bool = False,
+    scalar_types: Tuple[type, ...] = (),
+    sort_columns: bool = False,
+    unicode_form: Optional[str] = None,
+    unicode_names: bool = False,
+    validate_selection: bool = False,
+    coerce_objects: bool = False,
+    selection_mode: str = "exact",
+    dense_selection: bool = False,
+    transforms: Optional[Dict[JsonPath, Callable[[JsonValue], JsonValue]]] = None,
+    root_path: Optional[JsonPath] = None,
+    strict_root_path: bool = False,
+    distinct: bool = False,
+    nested_list_mode: str = "flatten",
+    detect_name_collisions: bool = False,
+    dedup_adjacent_segments: bool = False,
+    max_items: Optional[int] = None,
+    source_index: Optional[str] = None,
+) -> Union[NormalizeResult, Tuple[NormalizeResult, NormalizePaths]]:
+    """
+    Normalize a JSON value into a list of flat records.
+
+    Args:
+        obj: The JSON value to normalize.
+        separator: The separator used to join nested names.
+        fallback: The name of a value with an empty path (e.g. a scalar root).
+        selection_set: The optional set of paths to include (all paths otherwise).
+        prefix: An optional prefix prepended to every name.
+        array_marker: An optional marker appended to names that cross an array.
+        empty_list_as_null: Whether an empty list is emitted as a null.
+        empty_object_as_null: Whether an empty object is emitted as a null.
+        sanitize: An optional mode (`lower`, `snake` or `sql`) for sanitizing names.
+        max_rows: An optional limit on the number of rows produced by a join.
+        sibling_mode: How arrays that are siblings within an object are combined.
+
+            `product` means every element of each array is joined with every element
+            of the others (the number of rows is the product of their lengths).
+            `zip` means the arrays are aligned by index and, in the rows beyond the end
+            of a shorter array, its names are null (the number of rows is the length
+            of the longest).
+        aliases: An optional mapping of path segments to the names used in their place.
+        max_depth: An optional maximum depth of nesting before a RecursionError is
+            raised, where every object and array counts as a level (e.g. the `1` in
+            `{"a": [[1]]}` has a depth of 3).
+        with_paths: Whether to also return the mapping of each name to its path.
+        null_value: The value emitted in place of a null (the default is `None`).
+        list_delimiter: An optional delimiter used to join an array of scalars into a
+            single value (arrays containing other values are still exploded).
+
+            Nulls within the array are omitted (an array of only nulls is a null) and
+            booleans are written as `true` or `false` (e.g. `[1, None, True]` is
+            `1, true` with a delimiter of `, `).
+        drop_null_columns: Whether names that are null in every record are removed.
+        escape_separator: Whether the separator is escaped within path segments.
+
+            A backslash within a segment is doubled and each occurrence of the
+            separator is preceded by a backslash (e.g. `{"a.b": {"c": 1}}` is named
+            `a\\.b.c`), so a name can be split unambiguously on unescaped separators.
+        scalar_types: Additional types that are treated as scalars (other values that
+            are not JSON types are ignored).
+        sort_columns: Whether the names within each record are sorted alphabetically.
+        unicode_form: An optional Unicode normalization form (`NFC`, `NFD`, `NFKC` or
+            `NFKD`) applied to string values.
+        unicode_names: Whether the Unicode normalization form is also applied to names.
+        validate_selection: Whether to raise a ValueError if any path in the selection
+            set is never matched.
+        coerce_objects: Whether dataclasses and other objects with a `__dict__` are
+            treated as JSON objects of their public attributes (enums and iterable
+            objects are not).
+        selection_mode: How paths are matched against the selection set.
+
+            `exact` means the full path must be in the selection set.
+            `leaf` means the last segment of the path must be the last segment of a
+            path in the selection set (e.g. `id` selects every `id` at any depth).
+        dense_selection: Whether every path in the selection set produces a name, even
+            if it is never matched (only applies to the `exact` selection mode).
+
+            The name of a path that is never matched is only marked as crossing an
+            array (see `array_marker`) where an array was found in `obj`.
+        transforms: An optional mapping of paths to functions applied to their values.
+        root_path: An optional path of the object within `obj` to normalize (names and
+            the selection set still use the full path).
+        strict_root_path: Whether to raise a ValueError if the root path is not found
+            (otherwise it is normalized as an empty array).
+        distinct: Whether duplicate records are removed (keeping the first).
+        nested_list_mode: How arrays that contain arrays are normalized.
+
+            `flatten` means the inner arrays are exploded into rows like the outer.
+            `keep_inner` means the outer array is exploded and each inner array is
+            emitted as a single value.
+            `keep_all` means the outer array is emitted as a single value.
+        detect_name_collisions: Whether to raise a ValueError if different paths
+            produce the same name (e.g. `{"a.b": 1, "a": {"b": 2}}`).
+        dedup_adjacent_segments: Whether consecutive identical segments are collapsed
+            into one when generating names (e.g. `user.user.name` is `user.name`).
+        max_items: An optional limit on the number of items taken from an iterable
+            that is not a list (e.g. a generator) before a ValueError is raised.
+        source_index: An optional name for a value (the first in each record) that is
+            the index of the item of `obj` from which the record was produced (or `0`
+            if `obj` is not an array). It has an empty path and must not also be the
+            name of another value.
+    """
     assert separator
-    options = JsonNormalizerOptions(separator, fallback, selection_set=selection_set)
+    if selection_mode not in ("exact", "leaf"):
+        raise ValueError(f"{selection_mode} is not a valid value")
+    selection_leaves = None
+    if selection_mode == "leaf" and selection_set:
+        selection_leaves = frozenset(x[-1] for x in selection_set if x)
+    if unicode_form:
+        unicode_form = unicode_form.upper()
+        unicodedata.normalize(unicode_form, "")
+    if sibling_mode not in ("product", "zip"):
+        raise ValueError(f"{sibling_mode} is not a valid value")
+    if nested_list_mode not in ("flatten", "keep_inner", "keep_all"):
+        raise ValueError(f"{nested_list_mode} is not a valid value")
+    if root_path:
+        obj = _get_path(obj, root_path, strict_root_path)
+    options = JsonNormalizerOptions(
+        separator,
+        fallback,
+        initial_path=root_path,
+        selection_set=selection_set,
+        prefix=prefix,
+        array_marker=array_marker,
+        empty_list_as_null=empty_list_as_null,
+        empty_object_as_null=empty_object_as_null,
+        max_rows=max_rows,
+        sibling_mode=sibling_mode,
+        aliases=aliases,
+        max_depth=max_depth,
+        null_value=null_value,
+        list_delimiter=list_delimiter,
+        escape_separator=escape_separator,
+        scalar_types=scalar_types,
+        unicode_form=unicode_form,
+        unicode_names=unicode_names,
+        coerce_objects=coerce_objects,
+        selection_leaves=selection_leaves,
+        dense_selection=dense_selection,
+        transforms=transforms,
+        nested_list_mode=nested_list_mode,
+        detect_name_collisions=detect_name_collisions,
+        dedup_adjacent_segments=dedup_adjacent_segments,
+        max_items=max_items,
+        source_index=source_index,
+    )
     result = JsonNormalizer(options).normalize(obj)
-    return result.rows
+    if validate_selection and selection_set:
+        _validate_selection(selection_set, result.paths, selection_leaves is not None)
+    rows, names = result.rows, result.names
+    if drop_null_columns:
+        rows, names = _drop_null_columns(rows, names, result.valued_names)
+    if sanitize:
+        sanitizer = ColumnNameSanitizer(sanitize)
+        rows = sanitizer.sanitize(rows)
+        names = {sanitizer.get_name(k): v for k, v in names.items()}
+    if distinct:
+        rows = _distinct(rows)
+    if sort_columns:
+        rows = [dict(sorted(row.items())) for row in rows]
+        names = dict(sorted(names.items()))
+    if with_paths:
+        return rows, {k: list(v) for k, v in names.items()}
+    return rows
 
 
 @dataclass(frozen=True)
@@ -27,6 +226,32 @@ class JsonNormalizerOptions:
     fallback: str
     initial_path: Optional[JsonPath] = None
     selection_set: Optional[Set[JsonPath]] = None
+    prefix: Optional[str] = None
+    array_marker: Optional[str] = None
+    array_depths: FrozenSet[int] = frozenset()
+    array_level: int = 0
+    empty_list_as_null: bool = False
+    empty_object_as_null: bool = False
+    max_rows: Optional[int] = None
+    sibling_mode: str = "product"
+    aliases: Optional[Dict[str, str]] = None
+    max_depth: Optional[int] = None
+    null_value: JsonValue = None
+    list_delimiter: Optional[str] = None
+    escape_separator: bool = False
+    scalar_types: Tuple[type, ...] = ()
+    unicode_form: Optional[str] = None
+    unicode_names: bool = False
+    coerce_objects: bool = False
+    selection_leaves: Optional[FrozenSet[str]] = None
+    dense_selection: bool = False
+    transforms: Optional[Dict[JsonPath, Callable[[JsonValue], JsonValue]]] = None
+    nested_list_mode: str = "flatten"
+    detect_name_collisions: bool = False
+    dedup_adjacent_segments: bool = False
+    max_items: Optional[int] = None
+    source_index: Optional[str] = None
+    is_root: bool = True
 
     def replace(self, **kwargs) -> JsonNormalizerOptions:
         return replace(self, **kwargs)
@@ -36,6 +261,9 @@ class JsonNormalizerOptions:
 class JsonNormalizerResult:
     rows: List[Dict[str, JsonValue]]
     paths: Set[JsonPath]
+    names: Dict[str, JsonPath]
+    valued_names: Set[str]
+    array_paths: Set[JsonPath]
 
 
 class JsonNormalizer:
@@ -45,8 +273,34 @@ class JsonNormalizer:
     def normalize(self, obj: JsonValue) -> JsonNormalizerResult:
         ctx = JsonNormalizerContext(self._options)
         visitor = JsonNormalizerVisitor(ctx)
-        visit(obj, visitor, self._options.initial_path)
-        return JsonNormalizerResult(ctx.rows, ctx.paths)
+        visit(
+            obj,
+            visitor,
+            self._options.initial_path,
+            self._options.scalar_types,
+            self._options.coerce_objects,
+            self._options.max_items,
+        )
+        if self._is_dense_selection():
+            ctx.fill(self._options.selection_set, self._options.null_value)
+        if self._options.is_root and (name := self._options.source_index):
+            ctx.set_source_index(name)
+        return JsonNormalizerResult(
+            ctx.rows,
+            ctx.paths,
+            ctx.names,
+            ctx.valued_names,
+            ctx.array_paths,
+        )
+
+    def _is_dense_selection(self) -> bool:
+        options = self._options
+        return (
+            options.dense_selection
+            and options.is_root
+            and bool(options.selection_set)
+            and options.selection_leaves is None
+        )
 
 
 class JsonNormalizerContext:
@@ -54,6 +308,11 @@ class JsonNormalizerContext:
         self._options = options
         self._rows: List[JsonValue] = [{}]
         self._paths: Set[JsonPath] = set()
+        self._names: Dict[str, JsonPath] = {}
+        self._valued_names: Set[str] = set()
+        self._array_paths: Set[JsonPath] = set()
+        self._is_source_indexed = False
+        self._siblings: List[List[Iterable]] = []
 
     @property
     def options(self) -> JsonNormalizerOptions:
@@ -67,27 +326,135 @@ class JsonNormalizerContext:
     def paths(self) -> Set[JsonPath]:
         return self._paths
 
-    def set(self, path: JsonPath, value: JsonValue):
+    @property
+    def names(self) -> Dict[str, JsonPath]:
+        return self._names
+
+    @property
+    def valued_names(self) -> Set[str]:
+        return self._valued_names
+
+    @property
+    def array_paths(self) -> Set[JsonPath]:
+        return self._array_paths
+
+    def add_array(self, path: JsonPath):
+        self._array_paths.add(path)
+
+    def add_source_index(self, name: str, index: int, rows: List[Dict]) -> List[Dict]:
+        self._is_source_indexed = True
+        self._valued_names.add(name)
+        return [{name: index} | row for row in rows]
+
+    def set_source_index(self, name: str):
+        if name in self._names:
+            raise ValueError(f"The source index '{name}' is also the name of a value.")
+        if not self._is_source_indexed:
+            self._rows = self.add_source_index(name, 0, self._rows)
+        self._names = {name: JsonPath()} | self._names
+
+    def set(self, path: JsonPath, value: JsonValue, is_null: bool = False):
+        name = self.generate_name(path)
         for row in reversed(self._rows):
-            name = self._generate_name(path)
             row[name] = value
         self._paths.add(path)
+        self._add_name(name, path)
+        if not is_null:
+            self._valued_names.add(name)
 
-    def _generate_name(self, path: JsonPath) -> str:
+    def fill(self, paths: Set[JsonPath], value: JsonValue):
+        for path in sorted(paths - self._paths):
+            name = self.generate_name(path, self._get_array_depths(path))
+            for row in self._rows:
+                row.setdefault(name, value)
+            self._names.setdefault(name, path)
+
+    def _get_array_depths(self, path: JsonPath) -> FrozenSet[int]:
+        return frozenset(
+            i for i in range(1, len(path) + 1) if path[:i] in self._array_paths
+        )
+
+    def _add_name(self, name: str, path: JsonPath):
+        if self._options.detect_name_collisions:
+            existing = self._names.get(name)
+            if existing is not None and existing != path:
+                raise ValueError(
+                    f"The paths {list(existing)} and {list(path)} are both named "
+                    f"'{name}'."
+                )
+        self._names[name] = path
+
+    def generate_name(
+        self,
+        path: JsonPath,
+        array_depths: Optional[FrozenSet[int]] = None,
+    ) -> str:
         separator = self._options.separator
         fallback = self._options.fallback
+        if aliases := self._options.aliases:
+            path = JsonPath(*(aliases.get(x, x) for x in path))
+        if self._options.escape_separator:
+            path = JsonPath(*(_escape(x, separator) for x in path))
+        if array_marker := self._options.array_marker:
+            if array_depths is None:
+                array_depths = self._options.array_depths
+            path = _mark_arrays(path, array_marker, array_depths)
+        if self._options.dedup_adjacent_segments:
+            path = JsonPath(*(k for k, _ in itertools.groupby(path)))
+        if prefix := self._options.prefix:
+            path = JsonPath(prefix, *path)
         name = separator.join(path)
+        if self._options.unicode_names and (form := self._options.unicode_form):
+            name = unicodedata.normalize(form, name)
         return fallback if not name and fallback else name
 
     def normalize(self, path: JsonPath, other: JsonValue):
-        normalizer = JsonNormalizer(self._options.replace(initial_path=path))
+        options = self._options.replace(
+            initial_path=path,
+            is_root=False,
+            array_depths=self._options.array_depths | {len(path)},
+            array_level=self._options.array_level + 1,
+        )
+        normalizer = JsonNormalizer(options)
         result = normalizer.normalize(other)
         self._paths.update(result.paths)
+        self._valued_names.update(result.valued_names)
+        self._array_paths.update(result.array_paths)
+        for name, path in result.names.items():
+            self._add_name(name, path)
         return result
 
-    def cross_join(self, other: Generator):
-        if other := _spy(other):
-            self._rows = [x | y for x, y in itertools.product(self._rows, other)]
+    def begin_object(self):
+        if self._options.sibling_mode == "zip":
+            self._siblings.append([])
+
+    def end_object(self, path: JsonPath):
+        if self._options.sibling_mode == "zip":
+            arrays = self._siblings.pop()
+            self.cross_join(path, _zip(arrays, self._options.null_value))
+
+    def join(self, path: JsonPath, other: Generator):
+        if self._siblings:
+            self._siblings[-1].append(other)
+        else:
+            self.cross_join(path, other)
+
+    def cross_join(self, path: JsonPath, other: Generator):
+        if other := list(other):
+            if max_rows := self._options.max_rows:
+                self._check_max_rows(path, len(self._rows) * len(other), max_rows)
+            if len(other) == 1:
+                for row in self._rows:
+                    row.update(other[0])
+            else:
+                self._rows = [x | y for x, y in itertools.product(self._rows, other)]
+
+    @staticmethod
+    def _check_max_rows(path: JsonPath, count: int, max_rows: int):
+        if count > max_rows:
+            raise ValueError(
+                f"Joining '{path}' would produce {count} rows (max_rows is {max_rows})."
+            )
 
 
 class JsonNormalizerVisitor(JsonVisitor):
@@ -95,28 +462,252 @@ class JsonNormalizerVisitor(JsonVisitor):
         self._ctx = ctx
 
     def on_scalar(self, path: JsonPath, value: JsonScalar):
-        self._set(path, value)
+        self._check_depth(path)
+        self._set(path, self._normalize_unicode(value))
 
     def on_null(self, path: JsonPath):
-        self._set(path, None)
+        self._check_depth(path)
+        self._set_null(path)
+
+    def _is_kept_list(self, path: JsonPath, value: JsonArray) -> bool:
+        options = self._ctx.options
+        match options.nested_list_mode:
+            case "keep_inner":
+                return not options.is_root and path == options.initial_path
+            case "keep_all":
+                return any(map(is_array, value))
+        return False
 
-    def _set(self, path: JsonPath, value: JsonValue):
-        selection_set = self._ctx.options.selection_set
-        if not selection_set or path in selection_set:
-            self._ctx.set(path, value)
+    def _normalize_unicode(self, value: JsonScalar) -> JsonScalar:
+        if isinstance(value, str) and (form := self._ctx.options.unicode_form):
+            return unicodedata.normalize(form, value)
+        return value
+
+    def _is_joinable(self, value: JsonValue) -> bool:
+        scalar_types = (str, float, int, bool, *self._ctx.options.scalar_types)
+        return value is None or isinstance(value, scalar_types)
+
+    def _normalize_item(self, path: JsonPath, index: int, item: JsonValue):
+        rows = self._ctx.normalize(path, item).rows
+        options = self._ctx.options
+        if options.is_root and path == (options.initial_path or JsonPath()):
+            if name := options.source_index:
+                return self._ctx.add_source_index(name, index, rows)
+        return rows
+
+    def _check_depth(self, path: JsonPath):
+        options = self._ctx.options
+        max_depth = options.max_depth
+        if max_depth is not None and len(path) + options.array_level > max_depth:
+            raise RecursionError(f"maximum depth of {max_depth} exceeded")
+
+    def _set(self, path: JsonPath, value: JsonValue, is_null: bool = False):
+        if self._is_selected(path):
+            if (transforms := self._ctx.options.transforms) and path in transforms:
+                transformed = transforms[path](value)
+                is_null = transformed is None or (is_null and transformed is value)
+                value = transformed
+            self._ctx.set(path, value, is_null)
+
+    def _set_null(self, path: JsonPath):
+        self._set(path, self._ctx.options.null_value, is_null=True)
+
+    def _is_selected(self, path: JsonPath) -> bool:
+        options = self._ctx.options
+        if options.selection_leaves is not None:
+            return bool(path) and path[-1] in options.selection_leaves
+        return not options.selection_set or path in options.selection_set
+
+    def enter_object(self, path: JsonPath, value: JsonObject):
+        self._check_depth(path)
+        if not value and self._ctx.options.empty_object_as_null:
+            self._set_null(path)
+        self._ctx.begin_object()
+
+    def leave(self, path: JsonPath, value: JsonValue):
+        self._ctx.end_object(path)
 
     def enter_array(self, path: JsonPath, value: JsonArray):
+        self._check_depth(path)
+        self._ctx.add_array(path)
+        if not value and self._ctx.options.empty_list_as_null:
+            self._set_null(path)
+            return STOP
+        if self._is_kept_list(path, value):
+            self._set(path, value)
+            return STOP
+        delimiter = self._ctx.options.list_delimiter
+        if delimiter is not None and value and all(map(self._is_joinable, value)):
+            if values := [_to_str(x) for x in value if x is not None]:
+                self._set(path, delimiter.join(map(self._normalize_unicode, values)))
+            else:
+                self._set_null(path)
+            return STOP
         rows = (
-            self._ctx.normalize(path, item).rows for item in value if item is not None
+            self._normalize_item(path, index, item)
+            for index, item in enumerate(value)
+            if item is not None
         )
         other = itertools.chain(*rows)
-        self._ctx.cross_join(other)
+        self._ctx.join(path, other)
         return STOP
 
 
-def _spy(generator: Generator) -> Optional[Iterable]:
+def selection_from_example(obj: JsonValue) -> Set[JsonPath]:
+    """
+    Create a selection set with the path of every scalar (or null) within `obj`.
+    """
+    visitor = LeafPathsVisitor()
+    visit(obj, visitor)
+    return visitor.paths
+
+
+def column_name(
+    path: Iterable[str],
+    separator: str = ".",
+    fallback: Optional[str] = "?",
+) -> str:
+    """
+    Get the name that `normalize` would give to the value at `path`.
+
+    Args:
+        path: The path of the value.
+        separator: The separator used to join nested names.
+        fallback: The name of a value with an empty path (e.g. a scalar root).
+    """
+    assert separator
+    ctx = JsonNormalizerContext(JsonNormalizerOptions(separator, fallback))
+    return ctx.generate_name(JsonPath(*path))
+
+
+class LeafPathsVisitor(JsonVisitor):
+    def __init__(self):
+        self.paths: Set[JsonPath] = set()
+
+    def on_scalar(self, path: JsonPath, value: JsonScalar):
+        self.paths.add(path)
+
+    def on_null(self, path: JsonPath):
+        self.paths.add(path)
+
+
+class ColumnNameSanitizer:
+    def __init__(self, mode: str):
+        self._sanitize = self._get_sanitize(mode)
+        self._names: Dict[str, str] = {}
+        self._taken: Set[str] = set()
+
+    def sanitize(self, rows: NormalizeResult) -> NormalizeResult:
+        return [{self.get_name(k): v for k, v in row.items()} for row in rows]
+
+    def get_name(self, name: str) -> str:
+        if (new_name := self._names.get(name)) is None:
+            new_name = candidate = self._sanitize(name)
+            for i in itertools.count(1):
+                if new_name not in self._taken:
+                    break
+                new_name = f"{candidate}_{i}"
+            self._names[name] = new_name
+            self._taken.add(new_name)
+        return new_name
+
+    @staticmethod
+    def _get_sanitize(mode: str):
+        match mode:
+            case "lower":
+                return _to_lower
+            case "snake":
+                return _to_snake
+            case "sql":
+                return _to_sql
+            case _:
+                raise ValueError(f"{mode} is not a valid value")
+
+
+def _to_lower(name: str) -> str:
+    return name.lower()
+
+
+def _to_snake(name: str) -> str:
+    name = re.sub(r"(?<=[a-z0-9])(?=[A-Z])", "_", name)
+    return re.sub(r"\W+", "_", name).lower()
+
+
+def _to_sql(name: str) -> str:
+    name = re.sub(r"[^A-Za-z0-9_]+", "_", name).lower()
+    return f"_{name}" if name[:1].isdigit() else name
+
+
+def _get_path(obj: JsonValue, path: JsonPath, strict: bool) -> JsonValue:
+    for key in path:
+        obj = get_key(obj, key, MISSING) if isinstance(obj, dict) else MISSING
+        if obj is MISSING:
+            if strict:
+                raise ValueError(f"'{path}' was not found.")
+            return []
+    return obj
+
+
+def _validate_selection(
+    selection_set: Set[JsonPath],
+    paths: Set[JsonPath],
+    is_leaf_mode: bool,
+):
+    if is_leaf_mode:
+        selection_set = {JsonPath(*x[-1:]) for x in selection_set}
+        paths = {JsonPath(*x[-1:]) for x in paths}
+    if missing := selection_set - paths:
+        names = ", ".join(sorted(f"'{x}'" for x in missing))
+        raise ValueError(f"The selection set contains unmatched paths: {names}.")
+
+
+def _drop_null_columns(
+    rows: NormalizeResult,
+    names: Dict[str, JsonPath],
+    keep: Set[str],
+) -> Tuple[NormalizeResult, Dict[str, JsonPath]]:
+    rows = [{k: v for k, v in row.items() if k in keep} for row in rows]
+    return rows, {k: v for k, v in names.items() if k in keep}
+
+
+def _mark_arrays(path: JsonPath, marker: str, depths: FrozenSet[int]) -> JsonPath:
+    return JsonPath(
+        *(x + marker if i in depths else x for i, x in enumerate(path, start=1))
+    )
+
+
+def _escape(segment: str, separator: str) -> str:
+    return segment.replace("\\", "\\\\").replace(separator, "\\" + separator)
+
+
+def _distinct(rows: NormalizeResult) -> NormalizeResult:
+    seen = set()
+    result = []
+    for row in rows:
+        key = frozenset((k, type(v), _hashable(v)) for k, v in row.items())
+        if key not in seen:
+            seen.add(key)
+            result.append(row)
+    return result
+
+
+def _hashable(value: JsonValue) -> JsonValue:
     try:
-        head = next(generator)
-        return itertools.chain((head,), generator)
-    except StopIteration:
-        return None
+        hash(value)
+        return value
+    except TypeError:
+        return repr(value)
+
+
+def _to_str(value: JsonScalar) -> str:
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    return str(value)
+
+
+def _zip(arrays: List[Iterable], null_value: JsonValue) -> Generator:
+    arrays = [list(x) for x in arrays]
+    padding = [dict.fromkeys(itertools.chain(*x), null_value) for x in arrays]
+    for i in range(max(map(len, arrays), default=0)):
+        rows = (x[i] if i < len(x) else y for x, y in zip(arrays, padding))
+        yield dict(itertools.chain(*(x.items() for x in rows)))
@@ -13,6 +13,7 @@ class FrameInfo:
     path: JsonPath
     name: str
     selection_set: Set[JsonPath]
+    fields: List[JsonPath]
 
 
 @dataclass(frozen=True)
@@ -20,6 +21,7 @@ class ParsedQuery:
     query: str
     frames: List[FrameInfo]
     selection_set: Set[JsonPath]
+    fields: List[JsonPath]
 
     @property
     def is_implicit_mode(self):
@@ -43,16 +45,19 @@ class FrameInfoBuilder:
         self.path = path
         self.name = name
         self.selection_set = set()
+        self.fields = []
 
     def add_field(self, path: JsonPath):
         assert path[: len(self.path)] == self.path
-        self.selection_set.add(path[len(self.path) :])
-
-    def add_self(self):
-        self.selection_set.add(self.path[-1])
+        self._add(path[len(self.path) :])
 
     def build(self) -> FrameInfo:
-        return FrameInfo(self.path, self.name, self.selection_set)
+        return FrameInfo(self.path, self.name, self.selection_set, self.fields)
+
+    def _add(self, path: JsonPath):
+        if path not in self.selection_set:
+            self.selection_set.add(path)
+            self.fields.append(path)
 
 
 class ParsedQueryBuilder:
@@ -62,13 +67,16 @@ class ParsedQueryBuilder:
         self._current_frames = deque()
         self._frame_names = set()
         self._selection_set = set()
+        self._fields = []
 
     def is_current_frame(self, path: JsonPath):
         current = self._current_frames[0] if self._current_frames else None
         return current and current.path == path
 
     def add_path(self, path: JsonPath):
-        self._selection_set.add(path)
+        if path not in self._selection_set:
+            self._selection_set.add(path)
+            self._fields.append(path)
         for frame in self._current_frames:
             frame.add_field(path)
 
@@ -76,7 +84,10 @@ class ParsedQueryBuilder:
         name = path[-1]
         if name in self._frame_names:
             raise ValueError(f"Duplicate frame name: '{name}'!")
-        self._add_frame(FrameInfoBuilder(path, name))
+        frame = FrameInfoBuilder(path, name)
+        if path in self._selection_set:
+            frame.add_field(path)
+        self._add_frame(frame)
 
     def end_frame(self):
         self._current_frames.popleft()
@@ -89,6 +100,7 @@ class ParsedQueryBuilder:
             self._query,
             [f.build() for f in self._frames],
             self._selection_set,
+            self._fields,
         )
 
     def _add_frame(self, frame: FrameInfoBuilder):
@@ -1,4 +1,6 @@
-from pluck._json import JsonPath
+import pytest
+
+from pluck._json import JsonPath, get_path
 
 
 def test_new():
@@ -12,3 +14,29 @@ def test_add():
     actual = JsonPath("a", "b").add("c", "d")
     assert actual is not expected
     assert actual == expected
+
+
+@pytest.mark.parametrize(
+    "path, expected",
+    [
+        ([], {"a": {"b": 1}, "c": [{"d": 2}, {"d": 3}, {}], "e": {1: "f"}}),
+        (["a"], {"b": 1}),
+        (["a", "b"], 1),
+        (["a", "x"], None),
+        (["a", "b", "x"], None),
+        (["c"], [{"d": 2}, {"d": 3}, {}]),
+        (["c", "d"], [2, 3]),
+        (["c", "x"], []),
+        (["e", "1"], "f"),
+    ],
+)
+def test_get_path(path, expected):
+    obj = {"a": {"b": 1}, "c": [{"d": 2}, {"d": 3}, {}], "e": {1: "f"}}
+    actual = get_path(obj, path)
+    assert actual == expected
+
+
+def test_get_path_when_nested_arrays():
+    obj = {"a": [[{"b": 1}], [{"b": 2}, {"b": [3]}]]}
+    actual = get_path(obj, ["a", "b"])
+    assert actual == [1, 2, [3]]
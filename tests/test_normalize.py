@@ -1,9 +1,12 @@
+import dataclasses
+import datetime
+import enum
 import itertools
 
 import pytest
 
 from pluck._json import JsonPath
-from pluck._normalization import normalize
+from pluck._normalization import column_name, normalize, selection_from_example
 
 
 def scalars():
@@ -200,6 +203,16 @@ def fields():
             {"a.d": 3, "a.e": 4},
         ],
     )
+    yield (
+        "Non-string keys",
+        {
+            1: {"a": 2},
+            2: {"a": 3},
+        },
+        [
+            {"1.a": 2, "2.a": 3},
+        ],
+    )
 
 
 def dimensions():
@@ -479,6 +492,26 @@ def dimensions():
             {"a": 1, "b.c": 3, "d.e": 5},
         ],
     )
+    yield (
+        "Dimension: tuple of scalars",
+        {
+            "a": (1, 2),
+        },
+        [
+            {"a": 1},
+            {"a": 2},
+        ],
+    )
+    yield (
+        "Dimension: set of strings",
+        {
+            "a": {"c", "b"},
+        },
+        [
+            {"a": "b"},
+            {"a": "c"},
+        ],
+    )
 
 
 def pruning():
@@ -649,6 +682,20 @@ def test_pruning(name, obj, selection_set, expected):
     assert actual == expected
 
 
+def test_str_subclass_keys():
+    class Key(str, enum.Enum):
+        A = "a"
+
+    actual = normalize({Key.A: 1, 2: {Key.A: 3}})
+    assert actual == [{"a": 1, "2.a": 3}]
+
+
+def test_non_string_keys_when_collision():
+    obj = {"a": {1: "x", "1": "y"}}
+    with pytest.raises(ValueError, match="are both named '1'"):
+        normalize(obj)
+
+
 def test_custom_separator():
     obj = {
         "a": {
@@ -669,3 +716,744 @@ def test_no_fallback():
     obj = 42
     actual = normalize(obj, fallback=None)
     assert actual == [{"": 42}]
+
+
+def test_custom_prefix():
+    obj = {
+        "a": {
+            "b": 1,
+        }
+    }
+    actual = normalize(obj, prefix="p")
+    assert actual == [{"p.a.b": 1}]
+
+
+def test_custom_prefix_when_empty_path():
+    obj = 42
+    actual = normalize(obj, prefix="p")
+    assert actual == [{"p": 42}]
+
+
+def test_custom_array_marker():
+    obj = {
+        "a": 1,
+        "b": [
+            {
+                "c": [
+                    {"d": 2},
+                ],
+                "e": {"f": 3},
+            },
+        ],
+    }
+    actual = normalize(obj, array_marker="[]")
+    assert actual == [{"a": 1, "b[].c[].d": 2, "b[].e.f": 3}]
+
+
+def test_empty_list():
+    obj = {"a": 1, "b": []}
+    actual = normalize(obj)
+    assert actual == [{"a": 1}]
+
+
+def test_empty_list_as_null():
+    obj = {"a": 1, "b": []}
+    actual = normalize(obj, empty_list_as_null=True)
+    assert actual == [{"a": 1, "b": None}]
+
+
+def test_empty_object_as_null():
+    obj = {"a": 1, "b": {}}
+    actual = normalize(obj, empty_object_as_null=True)
+    assert actual == [{"a": 1, "b": None}]
+
+
+@pytest.mark.parametrize(
+    "mode, expected",
+    [
+        ("lower", [{"launchsite.sitename": 1, "1 id": 2}]),
+        ("snake", [{"launch_site_site_name": 1, "1_id": 2}]),
+        ("sql", [{"launchsite_sitename": 1, "_1_id": 2}]),
+    ],
+)
+def test_sanitize(mode, expected):
+    obj = {"launchSite": {"siteName": 1}, "1 id": 2}
+    actual = normalize(obj, sanitize=mode)
+    assert actual == expected
+
+
+def test_sanitize_disambiguates_collisions():
+    obj = [
+        {"A": {"b": 1}, "a": {"b": 2}},
+        {"a": {"b": 3}},
+    ]
+    actual = normalize(obj, sanitize="lower")
+    assert actual == [{"a.b": 1, "a.b_1": 2}, {"a.b_1": 3}]
+
+
+def test_sanitize_when_invalid_mode():
+    with pytest.raises(ValueError):
+        normalize({"a": 1}, sanitize="invalid")
+
+
+def test_max_rows():
+    obj = {
+        "a": [1, 2, 3],
+        "b": [4, 5, 6],
+    }
+    actual = normalize(obj, max_rows=9)
+    assert len(actual) == 9
+
+
+def test_max_rows_when_exceeded():
+    obj = {
+        "a": [1, 2, 3],
+        "b": [4, 5, 6],
+    }
+    with pytest.raises(ValueError, match="'b' would produce 9 rows"):
+        normalize(obj, max_rows=8)
+
+
+def test_sibling_mode_zip():
+    obj = {
+        "a": 1,
+        "b": [{"c": 2}, {"c": 3}],
+        "d": [{"e": 4}, {"e": 5}, {"e": 6}],
+        "f": [7],
+    }
+    actual = normalize(obj, sibling_mode="zip")
+    assert actual == [
+        {"a": 1, "b.c": 2, "d.e": 4, "f": 7},
+        {"a": 1, "b.c": 3, "d.e": 5, "f": None},
+        {"a": 1, "b.c": None, "d.e": 6, "f": None},
+    ]
+
+
+def test_sibling_mode_zip_with_null_value():
+    obj = {"a": [1, 2], "b": [3]}
+    actual = normalize(obj, sibling_mode="zip", null_value="")
+    assert actual == [{"a": 1, "b": 3}, {"a": 2, "b": ""}]
+
+
+def test_sibling_mode_zip_is_per_object():
+    obj = {
+        "a": {
+            "b": [1, 2],
+            "c": [3, 4],
+        },
+        "d": [5, 6],
+    }
+    actual = normalize(obj, sibling_mode="zip")
+    assert actual == [
+        {"a.b": 1, "a.c": 3, "d": 5},
+        {"a.b": 1, "a.c": 3, "d": 6},
+        {"a.b": 2, "a.c": 4, "d": 5},
+        {"a.b": 2, "a.c": 4, "d": 6},
+    ]
+
+
+def test_sibling_mode_when_invalid():
+    with pytest.raises(ValueError):
+        normalize({"a": 1}, sibling_mode="invalid")
+
+
+def test_aliases():
+    obj = {
+        "a": 1,
+        "l": [
+            {"n": {"s": "x"}},
+        ],
+    }
+    aliases = {"l": "launches", "n": "rocket", "s": "name"}
+    actual = normalize(obj, aliases=aliases)
+    assert actual == [{"a": 1, "launches.rocket.name": "x"}]
+
+
+def test_aliases_do_not_affect_selection_set():
+    obj = {"l": {"n": 1, "m": 2}}
+    aliases = {"l": "launch", "n": "name"}
+    actual = normalize(obj, selection_set={JsonPath("l", "n")}, aliases=aliases)
+    assert actual == [{"launch.name": 1}]
+
+
+@pytest.mark.parametrize(
+    "path, separator, fallback, expected",
+    [
+        (["a", "b"], ".", "?", "a.b"),
+        (["a", "b"], "_", "?", "a_b"),
+        ([], ".", "?", "?"),
+        ([], ".", "root", "root"),
+        ([], ".", None, ""),
+    ],
+)
+def test_column_name(path, separator, fallback, expected):
+    actual = column_name(path, separator, fallback)
+    assert actual == expected
+
+
+def test_column_name_matches_normalize():
+    obj = {"a": [{"b": {"c": 1}}], "d": 2}
+    (row,) = normalize(obj, separator="/")
+    assert list(row) == [
+        column_name(["a", "b", "c"], "/"),
+        column_name(["d"], "/"),
+    ]
+
+
+def test_max_depth():
+    obj = {"a": [[1]], "b": {"c": None}}
+    actual = normalize(obj, max_depth=3)
+    assert actual == [{"a": 1, "b.c": None}]
+
+
+@pytest.mark.parametrize(
+    "obj",
+    [
+        {"a": [[1]]},
+        {"a": {"b": {"c": None}}},
+        {"a": {"b": {"c": {}}}},
+    ],
+)
+def test_max_depth_when_exceeded(obj):
+    with pytest.raises(RecursionError, match="maximum depth of 2 exceeded"):
+        normalize(obj, max_depth=2)
+
+
+def test_max_depth_when_deeply_nested_objects():
+    obj = value = {}
+    for _ in range(1500):
+        value["a"] = value = {}
+    assert normalize(obj) == [{}]
+    with pytest.raises(RecursionError, match="maximum depth of 1000 exceeded"):
+        normalize(obj, max_depth=1000)
+
+
+def test_max_depth_when_deeply_nested_arrays():
+    obj = value = []
+    for _ in range(3000):
+        value.append(value := [])
+    with pytest.raises(RecursionError, match="maximum depth of 50 exceeded"):
+        normalize(obj, max_depth=50)
+
+
+def test_with_paths():
+    obj = {
+        "a": 1,
+        "b": [
+            {"c": 2},
+            {"c": 3},
+        ],
+    }
+    actual = normalize(obj, with_paths=True)
+    assert actual == (
+        [{"a": 1, "b.c": 2}, {"a": 1, "b.c": 3}],
+        {"a": ["a"], "b.c": ["b", "c"]},
+    )
+
+
+def test_with_paths_when_sanitized():
+    obj = {"A": {"b": 1}, "a": {"b": 2}}
+    _, actual = normalize(obj, sanitize="lower", with_paths=True)
+    assert actual == {"a.b": ["A", "b"], "a.b_1": ["a", "b"]}
+
+
+def test_null_value():
+    obj = {"a": 1, "b": None, "c": []}
+    actual = normalize(obj, null_value="", empty_list_as_null=True)
+    assert actual == [{"a": 1, "b": "", "c": ""}]
+
+
+def test_list_delimiter():
+    obj = {
+        "a": 1,
+        "tags": ["x", None, "y", 2],
+        "b": [{"c": 1}, {"c": 2}],
+    }
+    actual = normalize(obj, list_delimiter=", ")
+    assert actual == [
+        {"a": 1, "tags": "x, y, 2", "b.c": 1},
+        {"a": 1, "tags": "x, y, 2", "b.c": 2},
+    ]
+
+
+def test_list_delimiter_with_booleans():
+    obj = {"a": [True, False, None]}
+    actual = normalize(obj, list_delimiter="|")
+    assert actual == [{"a": "true|false"}]
+
+
+def test_list_delimiter_when_only_nulls():
+    obj = [{"a": [None, None], "b": 1}, {"a": [None], "b": 2}]
+    actual = normalize(obj, list_delimiter=",", null_value="n/a")
+    assert actual == [{"a": "n/a", "b": 1}, {"a": "n/a", "b": 2}]
+    actual = normalize(
+        obj,
+        list_delimiter=",",
+        null_value="n/a",
+        drop_null_columns=True,
+    )
+    assert actual == [{"b": 1}, {"b": 2}]
+
+
+def test_list_delimiter_with_scalar_types():
+    dates = [datetime.date(2024, 1, 1), datetime.date(2024, 1, 2)]
+    obj = {"a": dates}
+    actual = normalize(obj, list_delimiter=", ", scalar_types=(datetime.date,))
+    assert actual == [{"a": "2024-01-01, 2024-01-02"}]
+
+
+def test_drop_null_columns():
+    obj = [
+        {"a": 1, "b": None, "c": None},
+        {"a": 2, "b": 3, "c": None},
+    ]
+    actual = normalize(obj, drop_null_columns=True)
+    assert actual == [{"a": 1, "b": None}, {"a": 2, "b": 3}]
+
+
+@pytest.mark.parametrize("null_value", [0, "", "null"])
+def test_drop_null_columns_with_null_value(null_value):
+    obj = [
+        {"a": null_value, "b": None, "c": None, "d": []},
+        {"a": null_value, "b": 1, "c": None, "d": []},
+    ]
+    actual = normalize(
+        obj,
+        null_value=null_value,
+        drop_null_columns=True,
+        empty_list_as_null=True,
+    )
+    assert actual == [
+        {"a": null_value, "b": null_value},
+        {"a": null_value, "b": 1},
+    ]
+
+
+def test_escape_separator():
+    obj = {
+        "a.b": {"c": 1},
+        "a": {"b": {"c": 2}},
+        "d\\": 3,
+    }
+    actual = normalize(obj, escape_separator=True)
+    assert actual == [{"a\\.b.c": 1, "a.b.c": 2, "d\\\\": 3}]
+
+
+def test_scalar_types():
+    class Status(enum.Enum):
+        ACTIVE = 1
+
+    obj = {"a": 1, "status": Status.ACTIVE, "date": datetime.date(2024, 1, 1)}
+    actual = normalize(obj, scalar_types=(Status,))
+    assert actual == [{"a": 1, "status": Status.ACTIVE}]
+
+
+def test_sort_columns():
+    obj = [
+        {"c": 1, "a": {"b": 2}},
+        {"b": 3, "a": {"b": 4}},
+    ]
+    actual = normalize(obj, sort_columns=True)
+    assert [list(x) for x in actual] == [["a.b", "c"], ["a.b", "b"]]
+
+
+def test_unicode_form():
+    obj = {"cafe\u0301": "cafe\u0301"}
+    actual = normalize(obj, unicode_form="nfc")
+    assert actual == [{"cafe\u0301": "caf\u00e9"}]
+
+
+def test_unicode_form_with_list_delimiter():
+    obj = {"a": ["cafe\u0301", "x"]}
+    actual = normalize(obj, unicode_form="nfc", list_delimiter=",")
+    assert actual == [{"a": "caf\u00e9,x"}]
+
+
+def test_unicode_form_with_names():
+    obj = {"cafe\u0301": "cafe\u0301"}
+    actual = normalize(obj, unicode_form="nfc", unicode_names=True)
+    assert actual == [{"caf\u00e9": "caf\u00e9"}]
+
+
+def test_unicode_form_when_invalid():
+    with pytest.raises(ValueError):
+        normalize({"a": "b"}, unicode_form="invalid")
+
+
+def test_validate_selection():
+    obj = {"a": {"b": 1, "c": 2}}
+    selection_set = {JsonPath("a", "b")}
+    actual = normalize(obj, selection_set=selection_set, validate_selection=True)
+    assert actual == [{"a.b": 1}]
+
+
+def test_validate_selection_when_unmatched():
+    obj = {"a": {"b": 1}}
+    selection_set = {JsonPath("a", "b"), JsonPath("a", "x"), JsonPath("y")}
+    with pytest.raises(ValueError, match="'a.x', 'y'"):
+        normalize(obj, selection_set=selection_set, validate_selection=True)
+
+
+def test_coerce_objects():
+    @dataclasses.dataclass
+    class Rocket:
+        name: str
+
+    class Launch:
+        def __init__(self, rocket):
+            self.rocket = rocket
+
+    obj = {"a": 1, "launch": Launch(Rocket("Falcon 9"))}
+    assert normalize(obj) == [{"a": 1}]
+    actual = normalize(obj, coerce_objects=True)
+    assert actual == [{"a": 1, "launch.rocket.name": "Falcon 9"}]
+
+
+def test_coerce_objects_only_public_attributes():
+    @dataclasses.dataclass
+    class Rocket:
+        name: str
+        _secret: str = "x"
+
+    class Launch:
+        def __init__(self):
+            self.id = 1
+            self._cache = {"a": 2}
+            self.rocket = Rocket("Falcon 9")
+
+    actual = normalize(Launch(), coerce_objects=True)
+    assert actual == [{"id": 1, "rocket.name": "Falcon 9"}]
+
+
+def test_coerce_objects_when_not_plain_instances():
+    class Status(enum.Enum):
+        ACTIVE = 1
+
+    class Items:
+        def __init__(self):
+            self.a = 1
+
+        def __iter__(self):
+            return iter([{"b": 2}])
+
+    obj = {
+        "a": 1,
+        "status": Status.ACTIVE,
+        "items": Items(),
+        "type": Status,
+        "function": test_coerce_objects,
+        "method": Items().__iter__,
+        "module": enum,
+    }
+    actual = normalize(obj, coerce_objects=True, empty_object_as_null=True)
+    assert actual == [{"a": 1, "items.b": 2}]
+
+
+def test_selection_mode_leaf():
+    obj = {
+        "id": 1,
+        "name": "a",
+        "rocket": {"id": 2, "name": "b"},
+        "ships": [{"id": 3}, {"id": 4}],
+    }
+    actual = normalize(obj, selection_set={JsonPath("id")}, selection_mode="leaf")
+    assert actual == [
+        {"id": 1, "rocket.id": 2, "ships.id": 3},
+        {"id": 1, "rocket.id": 2, "ships.id": 4},
+    ]
+
+
+def test_selection_mode_leaf_with_validate_selection():
+    obj = {"rocket": {"id": 2}}
+    with pytest.raises(ValueError, match="'name'"):
+        normalize(
+            obj,
+            selection_set={JsonPath("x", "id"), JsonPath("name")},
+            selection_mode="leaf",
+            validate_selection=True,
+        )
+
+
+def test_selection_mode_when_invalid():
+    with pytest.raises(ValueError):
+        normalize({"a": 1}, selection_mode="invalid")
+
+
+def test_dense_selection():
+    obj = {"a": {"b": 1}, "c": [{"d": 2}, {"d": 3}]}
+    selection_set = {
+        JsonPath("a", "b"),
+        JsonPath("c", "e"),
+        JsonPath("c", "d"),
+        JsonPath("a", "f"),
+    }
+    actual = normalize(obj, selection_set=selection_set, dense_selection=True)
+    assert [list(x.items()) for x in actual] == [
+        [("a.b", 1), ("c.d", 2), ("a.f", None), ("c.e", None)],
+        [("a.b", 1), ("c.d", 3), ("a.f", None), ("c.e", None)],
+    ]
+
+
+def test_dense_selection_when_null():
+    obj = None
+    selection_set = {JsonPath("a", "b")}
+    actual = normalize(obj, selection_set=selection_set, dense_selection=True)
+    assert actual == [{"a.b": None}]
+
+
+def test_dense_selection_with_array_marker():
+    obj = {"c": [{"d": 1}], "f": []}
+    selection_set = {
+        JsonPath("c", "d"),
+        JsonPath("c", "e"),
+        JsonPath("f", "g"),
+        JsonPath("h", "i"),
+    }
+    actual = normalize(
+        obj,
+        selection_set=selection_set,
+        dense_selection=True,
+        array_marker="[]",
+    )
+    assert actual == [{"c[].d": 1, "c[].e": None, "f[].g": None, "h.i": None}]
+
+
+def test_transforms():
+    obj = {"a": [{"created_at": 0}, {"created_at": None}], "b": 1}
+    transforms = {
+        JsonPath("a", "created_at"): lambda x: x if x is None else str(x),
+    }
+    actual = normalize(obj, transforms=transforms)
+    assert actual == [
+        {"a.created_at": "0", "b": 1},
+        {"a.created_at": None, "b": 1},
+    ]
+
+
+def test_root_path():
+    obj = {
+        "a": 1,
+        "b": {
+            "c": [{"d": 2}, {"d": 3}],
+        },
+    }
+    actual = normalize(obj, root_path=JsonPath("b", "c"))
+    assert actual == [{"b.c.d": 2}, {"b.c.d": 3}]
+
+
+def test_root_path_when_not_found():
+    obj = {"a": {"b": 1}}
+    actual = normalize(obj, root_path=JsonPath("a", "x"))
+    assert actual == [{}]
+
+
+def test_root_path_when_not_found_with_dense_selection():
+    obj = {"a": 1}
+    selection_set = {JsonPath("b", "c")}
+    actual = normalize(
+        obj,
+        root_path=JsonPath("b"),
+        selection_set=selection_set,
+        dense_selection=True,
+    )
+    assert actual == [{"b.c": None}]
+    with pytest.raises(ValueError, match="'b.c'"):
+        normalize(
+            obj,
+            root_path=JsonPath("b"),
+            selection_set=selection_set,
+            validate_selection=True,
+        )
+
+
+def test_root_path_with_non_string_keys():
+    obj = {1: {"b": 2}}
+    actual = normalize(obj, root_path=JsonPath("1"))
+    assert actual == [{"1.b": 2}]
+
+
+def test_strict_root_path_when_not_found():
+    obj = {"a": {"b": 1}}
+    with pytest.raises(ValueError, match="'a.x' was not found"):
+        normalize(obj, root_path=JsonPath("a", "x"), strict_root_path=True)
+
+
+def test_distinct():
+    obj = {
+        "a": 1,
+        "b": [{"c": 2}, {"c": 3}, {"c": 2}],
+        "d": [{"e": 4}, {"e": 4}],
+    }
+    actual = normalize(obj, distinct=True)
+    assert actual == [
+        {"a": 1, "b.c": 2, "d.e": 4},
+        {"a": 1, "b.c": 3, "d.e": 4},
+    ]
+
+
+def test_distinct_compares_types():
+    obj = [{"a": 1}, {"a": True}, {"a": 1.0}, {"a": 1}]
+    actual = normalize(obj, distinct=True)
+    assert [type(x["a"]) for x in actual] == [int, bool, float]
+
+
+@pytest.mark.parametrize(
+    "mode, expected",
+    [
+        (
+            "flatten",
+            [
+                {"a": 1, "m": 1},
+                {"a": 1, "m": 2},
+                {"a": 1, "m": 3},
+                {"a": 1, "m": 4},
+            ],
+        ),
+        (
+            "keep_inner",
+            [
+                {"a": 1, "m": [1, 2]},
+                {"a": 1, "m": [3, 4]},
+            ],
+        ),
+        (
+            "keep_all",
+            [
+                {"a": 1, "m": [[1, 2], [3, 4]]},
+            ],
+        ),
+    ],
+)
+def test_nested_list_mode(mode, expected):
+    obj = {"a": 1, "m": [[1, 2], [3, 4]]}
+    actual = normalize(obj, nested_list_mode=mode)
+    assert actual == expected
+
+
+def test_distinct_with_nested_list_mode():
+    obj = {"a": [[1, 2], [3], [1, 2]], "b": [{"c": {"d": 1}}, {"c": {"d": 1}}]}
+    transforms = {JsonPath("b", "c", "d"): lambda x: {"e": [x]}}
+    actual = normalize(
+        obj,
+        nested_list_mode="keep_inner",
+        transforms=transforms,
+        distinct=True,
+    )
+    assert actual == [
+        {"a": [1, 2], "b.c.d": {"e": [1]}},
+        {"a": [3], "b.c.d": {"e": [1]}},
+    ]
+
+
+def test_nested_list_mode_when_invalid():
+    with pytest.raises(ValueError):
+        normalize({"a": 1}, nested_list_mode="invalid")
+
+
+def test_detect_name_collisions():
+    obj = {"a": [{"b": 1}], "c": {"b": 2}}
+    actual = normalize(obj, detect_name_collisions=True)
+    assert actual == [{"a.b": 1, "c.b": 2}]
+
+
+@pytest.mark.parametrize(
+    "obj",
+    [
+        {"a.b": 1, "a": {"b": 2}},
+        {"a": [{"b": 1}], "a.b": 2},
+    ],
+)
+def test_detect_name_collisions_when_collision(obj):
+    with pytest.raises(ValueError, match="are both named 'a.b'"):
+        normalize(obj, detect_name_collisions=True)
+
+
+def test_selection_from_example():
+    obj = {
+        "a": 1,
+        "b": [
+            {"c": 2},
+            {"d": None, "e": {"f": "g"}},
+        ],
+        "h": [],
+    }
+    actual = selection_from_example(obj)
+    assert actual == {
+        JsonPath("a"),
+        JsonPath("b", "c"),
+        JsonPath("b", "d"),
+        JsonPath("b", "e", "f"),
+    }
+    assert normalize(obj, selection_set=actual) == normalize(obj)
+
+
+def test_dedup_adjacent_segments():
+    obj = {"user": {"user": {"name": "a", "id": {"id": 1}}}}
+    actual = normalize(obj, dedup_adjacent_segments=True, with_paths=True)
+    assert actual == (
+        [{"user.name": "a", "user.id": 1}],
+        {
+            "user.name": ["user", "user", "name"],
+            "user.id": ["user", "user", "id", "id"],
+        },
+    )
+
+
+def test_iterables():
+    obj = {
+        "a": (x for x in [1, 2]),
+        "b": range(3, 4),
+        "c": b"bytes",
+    }
+    actual = normalize(obj)
+    assert actual == [{"a": 1, "b": 3}, {"a": 2, "b": 3}]
+
+
+def test_max_items():
+    obj = {"a": (x for x in [1, 2, 3])}
+    actual = normalize(obj, max_items=3)
+    assert actual == [{"a": 1}, {"a": 2}, {"a": 3}]
+
+
+def test_max_items_when_exceeded():
+    obj = {"a": itertools.count()}
+    with pytest.raises(ValueError, match="more than 3 items"):
+        normalize(obj, max_items=3)
+
+
+def test_source_index():
+    obj = [
+        {"a": 1, "b": [{"c": 2}, {"c": 3}]},
+        None,
+        {"a": 4, "b": [[{"c": 5}]]},
+    ]
+    actual = normalize(obj, source_index="index", drop_null_columns=True)
+    assert [list(x.items()) for x in actual] == [
+        [("index", 0), ("a", 1), ("b.c", 2)],
+        [("index", 0), ("a", 1), ("b.c", 3)],
+        [("index", 2), ("a", 4), ("b.c", 5)],
+    ]
+
+
+def test_source_index_when_not_array():
+    obj = {"a": 1, "b": [{"c": 2}, {"c": 3}]}
+    actual = normalize(obj, source_index="index")
+    assert actual == [
+        {"index": 0, "a": 1, "b.c": 2},
+        {"index": 0, "a": 1, "b.c": 3},
+    ]
+
+
+def test_source_index_with_paths():
+    obj = [{"a": 1}, {"a": 2}]
+    actual = normalize(obj, source_index="index", with_paths=True)
+    assert actual == (
+        [{"index": 0, "a": 1}, {"index": 1, "a": 2}],
+        {"index": [], "a": ["a"]},
+    )
+
+
+@pytest.mark.parametrize("obj", [[{"index": "x"}], {"index": "x"}])
+def test_source_index_when_collision(obj):
+    with pytest.raises(ValueError, match="'index' is also the name of a value"):
+        normalize(obj, source_index="index")
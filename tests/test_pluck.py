@@ -58,6 +58,105 @@ def test_when_custom_client():
     assert actual.data == {"field": "value"}
 
 
+def test_when_frames_are_empty():
+    client = MockGraphQLClient({"data": {"cores": None}})
+
+    cores, missions = pluck.execute(
+        "{ cores @frame { id status missions @frame { name } } }",
+        client=client,
+        url="http://spacex/graphql",
+    )
+
+    assert len(cores) == 0
+    assert list(cores.columns) == ["id", "status", "missions.name"]
+    assert len(missions) == 0
+    assert list(missions.columns) == ["name"]
+
+
+def test_when_frame_items_are_empty():
+    client = MockGraphQLClient({"data": {"launches": [{"ships": []}, {"ships": []}]}})
+
+    (ships,) = pluck.execute(
+        "{ launches { ships @frame { name home_port } } }",
+        client=client,
+        url="http://spacex/graphql",
+    )
+
+    assert len(ships) == 0
+    assert list(ships.columns) == ["name", "home_port"]
+
+
+def test_when_some_frame_items_are_empty():
+    launches = [{"ships": []}, {"ships": [{"name": "GO Ms Tree", "home_port": None}]}]
+    client = MockGraphQLClient({"data": {"launches": launches}})
+
+    (ships,) = pluck.execute(
+        "{ launches { ships @frame { name home_port } } }",
+        client=client,
+        url="http://spacex/graphql",
+    )
+
+    assert len(ships) == 1
+    assert list(ships.columns) == ["name", "home_port"]
+
+
+def test_when_implicit_frame_is_empty():
+    client = MockGraphQLClient({"data": {"launches": []}})
+
+    (default,) = pluck.execute(
+        "{ launches { id mission_name } }",
+        client=client,
+        url="http://spacex/graphql",
+    )
+
+    assert len(default) == 0
+    assert list(default.columns) == ["launches.id", "launches.mission_name"]
+
+
+def test_when_scalar_frame_is_empty():
+    client = MockGraphQLClient({"data": {"cores": []}})
+
+    (status,) = pluck.execute(
+        "{ cores { status @frame } }",
+        client=client,
+        url="http://spacex/graphql",
+    )
+
+    assert len(status) == 0
+    assert list(status.columns) == ["status"]
+
+
+def test_when_frame_records_are_empty():
+    client = MockGraphQLClient({"data": {"ships": [{}, {}]}})
+
+    (ships,) = pluck.execute(
+        "{ ships @frame { name home_port } }",
+        client=client,
+        url="http://spacex/graphql",
+    )
+
+    assert len(ships) == 2
+    assert list(ships.columns) == ["name", "home_port"]
+
+
+def test_when_subtree_cannot_contain_a_frame():
+    class PoisonedDict(dict):
+        def items(self):
+            raise AssertionError("The subtree should not be visited.")
+
+    data = {"cores": [{"id": "B1015"}], "ships": PoisonedDict(name="GO Ms Tree")}
+    client = MockGraphQLClient({"data": data})
+
+    (cores,) = pluck.execute(
+        "{ cores @frame { id } ships { name } }",
+        client=client,
+        url="http://spacex/graphql",
+    )
+
+    assert list(cores.columns) == ["id"]
+    assert len(cores) == 1
+
+
 def test_url_must_be_specified():
     with pytest.raises(AssertionError):
         pluck.execute("{ field }", url=None)